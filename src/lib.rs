@@ -2,12 +2,272 @@
 
 use std::any::Any;
 use std::boxed::Box;
+use std::collections::HashMap;
 use std::ffi::c_void;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
 use wamr_rust_sdk::RuntimeError;
 use wamr_rust_sdk::function::Function;
 use wamr_rust_sdk::instance::Instance;
 use wamr_rust_sdk::value::WasmValue;
 
+/// Monotonically increasing id handed out to every in-flight pthread so handles can be
+/// joined in any order, independent of spawn order.
+static NEXT_HANDLE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Raw `thread` ids for pthreads that have been spawned but not yet joined, keyed by the
+/// handle id that was assigned when they were spawned.
+static INFLIGHT_THREADS: OnceLock<Mutex<HashMap<u64, u32>>> = OnceLock::new();
+
+fn inflight_threads() -> &'static Mutex<HashMap<u64, u32>> {
+    INFLIGHT_THREADS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn register_inflight_thread(thread: u32) -> u64 {
+    let id = NEXT_HANDLE_ID.fetch_add(1, Ordering::Relaxed);
+    inflight_threads().lock().unwrap().insert(id, thread);
+    id
+}
+
+/// Hands out a handle id for a detached thread without registering its raw `thread` id in
+/// `INFLIGHT_THREADS`. A detached thread can exit and have its numeric id recycled by an
+/// unrelated joinable thread at any time, so nothing may join or `pthread_detach` it again
+/// once it's been created — the id is only ever used to make `PThreadHandle<T>` generic
+/// over both cases.
+fn next_detached_handle_id() -> u64 {
+    NEXT_HANDLE_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Exit code used for `RuntimeError::ExecutionError`s raised because a pthread closure
+/// panicked, distinguishing them from thread create/join failures.
+const PTHREAD_PANIC_EXIT_CODE: u32 = 101;
+
+/// Sentinel boxed in place of a closure's real result when [`std::panic::catch_unwind`]
+/// caught it panicking, so a panic can be told apart from an ordinary type mismatch when
+/// the result is downcast.
+struct PanicSentinel(String);
+
+/// Best-effort extraction of a human-readable message from a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+fn panic_runtime_error(message: String) -> RuntimeError {
+    RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
+        message: format!("pthread closure panicked: {}", message),
+        exit_code: PTHREAD_PANIC_EXIT_CODE,
+    })
+}
+
+/// Joins the pthread registered under `id`, downcasting its boxed result to `T`.
+///
+/// # Panics
+/// Panics if `id` is not present in the registry, which only happens if a `PThreadHandle`
+/// is joined twice.
+fn join_inflight_thread<T: Send + 'static>(id: u64) -> Result<T, RuntimeError> {
+    let thread = inflight_threads()
+        .lock()
+        .unwrap()
+        .remove(&id)
+        .expect("PThreadHandle joined twice");
+
+    unsafe {
+        let mut raw_ret = std::ptr::null_mut();
+        let join_ret = esp_idf_svc::sys::pthread_join(thread as _, &mut raw_ret);
+        if join_ret != 0 {
+            return Err(RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
+                message: format!("Failed to join thread: {}", join_ret),
+                exit_code: join_ret.abs() as u32,
+            }));
+        }
+
+        let boxed_result: Box<dyn Any + Send> = Box::from_raw(raw_ret as *mut _);
+        let boxed_result = match boxed_result.downcast::<PanicSentinel>() {
+            Ok(sentinel) => return Err(panic_runtime_error(sentinel.0)),
+            Err(boxed_result) => boxed_result,
+        };
+        let result_boxed_t: Box<T> = boxed_result.downcast::<T>().map_err(|_| {
+            RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
+                message: "Type mismatch in thread result".to_string(),
+                exit_code: 1,
+            })
+        })?;
+        Ok(*result_boxed_t)
+    }
+}
+
+/// A handle to a pthread spawned by [`spawn_pthread`] (or the `Function` pthread entry
+/// points), returned without blocking so the caller can run other work before collecting
+/// the result.
+///
+/// Handles are tracked independently of one another, so they can be joined in any order,
+/// not just the order they were spawned in.
+///
+/// A handle backed by a thread spawned with [`PThreadConfig::with_detached`] set is never
+/// joinable — see [`PThreadHandle::join`].
+pub struct PThreadHandle<T> {
+    id: u64,
+    detached: bool,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Send + 'static> PThreadHandle<T> {
+    /// Blocks until the pthread backing this handle finishes, then returns its result.
+    ///
+    /// This performs the `pthread_join`/downcast that used to be inlined directly in
+    /// `call_pthread`.
+    ///
+    /// # Errors
+    /// Returns `Err(RuntimeError::ExecutionError)` without touching the underlying thread
+    /// if this handle was created with [`PThreadConfig::with_detached`] set: a detached
+    /// thread cannot be joined (POSIX/ESP-IDF both reject it), and may have already exited
+    /// and had its numeric thread id recycled by an unrelated thread by the time `join` is
+    /// called.
+    pub fn join(self) -> Result<T, RuntimeError> {
+        if self.detached {
+            return Err(RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
+                message: "cannot join a PThreadHandle created with PThreadConfig::with_detached(true)"
+                    .to_string(),
+                exit_code: 1,
+            }));
+        }
+        join_inflight_thread(self.id)
+    }
+}
+
+impl<T> Drop for PThreadHandle<T> {
+    /// Detaches the underlying pthread if it was never joined, so dropping a handle
+    /// without calling [`PThreadHandle::join`] can't leak the OS thread (or its entry in
+    /// the inflight-thread registry) forever.
+    ///
+    /// A no-op for a handle whose thread was already created detached: it's never
+    /// registered in the inflight-thread registry in the first place (see
+    /// [`PThreadConfig::with_detached`]), so there's nothing here to detach or reclaim.
+    fn drop(&mut self) {
+        if self.detached {
+            return;
+        }
+        if let Some(thread) = inflight_threads().lock().unwrap().remove(&self.id) {
+            unsafe {
+                esp_idf_svc::sys::pthread_detach(thread as _);
+            }
+        }
+    }
+}
+
+/// Configuration for a pthread spawned by this crate: stack size, detach state,
+/// scheduling priority, and ESP32 core affinity.
+///
+/// Build one with [`PThreadConfig::new`] and the `with_*` methods, or pass a bare `i32`
+/// stack size anywhere a `PThreadConfig` is expected — it converts via `Into` so existing
+/// call sites that only cared about stack size keep working unchanged.
+#[derive(Debug, Clone, Copy)]
+pub struct PThreadConfig {
+    stacksize: i32,
+    detached: bool,
+    priority: Option<i32>,
+    core_id: Option<i32>,
+}
+
+impl PThreadConfig {
+    /// Starts a config with the given stack size, joinable, no priority or core override.
+    pub fn new(stacksize: i32) -> Self {
+        PThreadConfig {
+            stacksize,
+            detached: false,
+            priority: None,
+            core_id: None,
+        }
+    }
+
+    /// Overrides the stack size set by [`PThreadConfig::new`], in bytes.
+    pub fn with_stacksize(mut self, stacksize: i32) -> Self {
+        self.stacksize = stacksize;
+        self
+    }
+
+    /// Sets whether the pthread is created detached (`true`) or joinable (`false`, the
+    /// default).
+    ///
+    /// A handle backed by a detached thread can never be joined: [`PThreadHandle::join`]
+    /// returns `Err` immediately instead of touching the thread, since a detached thread
+    /// may already have exited and had its numeric id recycled by an unrelated joinable
+    /// thread by the time `join` would run. Only set this for threads whose result you
+    /// genuinely don't need back, such as fire-and-forget work; for anything whose result
+    /// (or completion) you need to observe, leave this `false` and call
+    /// [`PThreadHandle::join`] instead.
+    pub fn with_detached(mut self, detached: bool) -> Self {
+        self.detached = detached;
+        self
+    }
+
+    /// Sets the pthread's ESP-IDF scheduling priority, applied via `esp_pthread_set_cfg`.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Pins the pthread to the given ESP32 core id, applied via `esp_pthread_set_cfg`.
+    pub fn with_core_id(mut self, core_id: i32) -> Self {
+        self.core_id = Some(core_id);
+        self
+    }
+}
+
+impl From<i32> for PThreadConfig {
+    /// Lets existing call sites keep passing a bare stack size in bytes.
+    fn from(stacksize: i32) -> Self {
+        PThreadConfig::new(stacksize)
+    }
+}
+
+/// Builds the `pthread_attr_t` for a pthread, honoring the config's stack size and detach
+/// state.
+fn build_pthread_attr(config: &PThreadConfig) -> esp_idf_svc::sys::pthread_attr_t {
+    let mut attr = esp_idf_svc::sys::pthread_attr_t::default();
+    attr.stacksize = config.stacksize;
+    attr.detachstate = if config.detached {
+        esp_idf_svc::sys::PTHREAD_CREATE_DETACHED as i32
+    } else {
+        esp_idf_svc::sys::PTHREAD_CREATE_JOINABLE as i32
+    };
+    attr
+}
+
+/// Applies the config's scheduling priority and ESP32 core affinity, if either is set, via
+/// `esp_pthread_set_cfg`. This only affects the *next* pthread created on this thread,
+/// following ESP-IDF's `esp_pthread_cfg_t`/`xTaskCreatePinnedToCore` model. A no-op if
+/// neither `priority` nor `core_id` is set.
+fn apply_pthread_cfg(config: &PThreadConfig) -> Result<(), RuntimeError> {
+    if config.priority.is_none() && config.core_id.is_none() {
+        return Ok(());
+    }
+
+    let mut cfg = unsafe { esp_idf_svc::sys::esp_pthread_get_default_config() };
+    if let Some(priority) = config.priority {
+        cfg.prio = priority;
+    }
+    if let Some(core_id) = config.core_id {
+        cfg.pin_to_core = core_id;
+    }
+
+    let res = unsafe { esp_idf_svc::sys::esp_pthread_set_cfg(&cfg) };
+    if res != 0 {
+        return Err(RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
+            message: format!("Failed to apply pthread config: {}", res),
+            exit_code: res.abs() as u32,
+        }));
+    }
+    Ok(())
+}
+
 /// A trait that extends the `Function` type to enable calling WebAssembly functions in a pthread context.
 ///
 /// This trait is particularly useful for embedded systems like the ESP32, where executing WebAssembly
@@ -24,7 +284,7 @@ use wamr_rust_sdk::value::WasmValue;
 ///
 /// let params: Vec<WasmValue> = vec![];
 /// let result = function
-/// .call_pthread(&instance, &params)
+/// .call_pthread(&instance, &params, 4096)
 /// .expect("Failed to call WAMR function in pthread context");
 /// ```
 pub trait PThreadExtension<'instance> {
@@ -32,16 +292,36 @@ pub trait PThreadExtension<'instance> {
         &self,
         instance: &'instance Instance<'instance>,
         params: &Vec<WasmValue>,
+        config: impl Into<PThreadConfig>,
     ) -> Result<Vec<WasmValue>, RuntimeError>;
+
+    /// Batch counterpart to `call_pthread`: runs each job's function call on its own
+    /// pthread concurrently, joins all of them, and returns their results in the same
+    /// order the jobs were given. See the free [`call_pthread_parallel`] for the
+    /// closure-based equivalent, including how errors from a failed spawn/join are
+    /// handled.
+    fn call_pthread_parallel(
+        jobs: Vec<PThreadFunctionJob<'instance>>,
+    ) -> Result<Vec<Result<Vec<WasmValue>, RuntimeError>>, RuntimeError>
+    where
+        Self: Sized;
+}
+
+/// One job in a `Function::call_pthread_parallel` batch.
+pub struct PThreadFunctionJob<'instance> {
+    pub function: &'instance Function<'instance>,
+    pub instance: &'instance Instance<'instance>,
+    pub params: &'instance Vec<WasmValue>,
+    pub config: PThreadConfig,
 }
 
-struct FunctionCaller<'instance> {
+struct FunctionCaller<'instance, 'p> {
     function: &'instance Function<'instance>,
     instance: &'instance Instance<'instance>,
-    params: &'instance Vec<WasmValue>,
+    params: &'p Vec<WasmValue>,
 }
 
-impl<'instance> FunctionCaller<'instance> {
+impl<'instance, 'p> FunctionCaller<'instance, 'p> {
     fn call(&self) -> Result<Vec<WasmValue>, RuntimeError> {
         self.function.call(self.instance, self.params)
     }
@@ -49,10 +329,60 @@ impl<'instance> FunctionCaller<'instance> {
 
 unsafe extern "C" fn raw_fncaller(mut _arg: *mut std::ffi::c_void) -> *mut std::ffi::c_void {
     let fncaller = unsafe { Box::from_raw(_arg as *mut FunctionCaller) };
-    let ret: Box<Result<Vec<WasmValue>, RuntimeError>> = Box::new(fncaller.call());
+    // Calling into WAMR/user code here runs across the pthread trampoline's `extern "C"`
+    // boundary; a panic must not be allowed to unwind across it, so it's caught and turned
+    // into an `Err` in the same `Result` a failed call returns, carrying the panic error
+    // built by `panic_runtime_error`.
+    let result = match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| fncaller.call()))
+    {
+        Ok(result) => result,
+        Err(payload) => Err(panic_runtime_error(panic_message(&*payload))),
+    };
+    let ret: Box<Result<Vec<WasmValue>, RuntimeError>> = Box::new(result);
     Box::into_raw(ret) as *mut std::ffi::c_void
 }
 
+/// Spawns the pthread backing `Function::call_pthread` without joining it.
+fn spawn_function_pthread<'instance, 'p>(
+    function: &'instance Function<'instance>,
+    instance: &'instance Instance<'instance>,
+    params: &'p Vec<WasmValue>,
+    config: PThreadConfig,
+) -> Result<PThreadHandle<Result<Vec<WasmValue>, RuntimeError>>, RuntimeError> {
+    apply_pthread_cfg(&config)?;
+
+    let mut thread: u32 = 0;
+    let attr = build_pthread_attr(&config);
+
+    let fncaller = FunctionCaller {
+        function,
+        instance,
+        params,
+    };
+    let ptr_fncaller = Box::into_raw(Box::new(fncaller)) as *mut std::ffi::c_void;
+
+    let res = unsafe {
+        esp_idf_svc::sys::pthread_create(&mut thread, &attr, Some(raw_fncaller), ptr_fncaller)
+    };
+
+    if res != 0 {
+        return Err(RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
+            message: format!("Failed to create thread: {}", res),
+            exit_code: res.abs() as u32,
+        }));
+    }
+
+    Ok(PThreadHandle {
+        id: if config.detached {
+            next_detached_handle_id()
+        } else {
+            register_inflight_thread(thread)
+        },
+        detached: config.detached,
+        _marker: PhantomData,
+    })
+}
+
 impl<'instance> PThreadExtension<'instance> for Function<'instance> {
     /// Calls a WebAssembly function in a pthread context and returns the execution result.
     ///
@@ -89,7 +419,7 @@ impl<'instance> PThreadExtension<'instance> for Function<'instance> {
     /// use wamr_rust_sdk::value::WasmValue;
     ///
     /// let params: Vec<WasmValue> = vec![];
-    /// match function.call_pthread(&instance, &params) {
+    /// match function.call_pthread(&instance, &params, 4096) {
     /// Ok(results) => println!("Function returned: {:?}", results),
     /// Err(e) => eprintln!("Error calling function: {:?}", e),
     /// }
@@ -98,45 +428,55 @@ impl<'instance> PThreadExtension<'instance> for Function<'instance> {
         &self,
         instance: &'instance Instance<'instance>,
         params: &Vec<WasmValue>,
+        config: impl Into<PThreadConfig>,
     ) -> Result<Vec<WasmValue>, RuntimeError> {
-        let mut thread: u32 = 0;
-
-        let mut attr = esp_idf_svc::sys::pthread_attr_t::default();
-        attr.stacksize = 4096;
-        attr.detachstate = esp_idf_svc::sys::PTHREAD_CREATE_JOINABLE as i32;
+        spawn_function_pthread(self, instance, params, config.into())?.join()?
+    }
 
-        let fncaller = FunctionCaller {
-            function: &self,
-            instance,
-            params,
-        };
-        let ptr_fncaller = Box::into_raw(Box::new(fncaller)) as *mut std::ffi::c_void;
+    fn call_pthread_parallel(
+        jobs: Vec<PThreadFunctionJob<'instance>>,
+    ) -> Result<Vec<Result<Vec<WasmValue>, RuntimeError>>, RuntimeError> {
+        let mut handles: Vec<Option<PThreadHandle<Result<Vec<WasmValue>, RuntimeError>>>> =
+            Vec::with_capacity(jobs.len());
+        let mut spawn_err = None;
 
-        let res = unsafe {
-            esp_idf_svc::sys::pthread_create(&mut thread, &attr, Some(raw_fncaller), ptr_fncaller)
-        };
+        for job in jobs {
+            if spawn_err.is_some() {
+                handles.push(None);
+                continue;
+            }
 
-        if res != 0 {
-            return Err(RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
-                message: format!("Failed to create thread: {}", res),
-                exit_code: res.abs() as u32,
-            }));
-        } else {
-            unsafe {
-                let mut raw_ret = std::ptr::null_mut();
-                let join_ret = esp_idf_svc::sys::pthread_join(thread as _, &mut raw_ret);
-                if join_ret != 0 {
-                    return Err(RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
-                        message: format!("Failed to join thread: {}", join_ret),
-                        exit_code: join_ret.abs() as u32,
-                    }));
+            match spawn_function_pthread(job.function, job.instance, job.params, job.config) {
+                Ok(handle) => handles.push(Some(handle)),
+                Err(err) => {
+                    spawn_err = Some(err);
+                    handles.push(None);
                 }
+            }
+        }
 
-                let instance_data =
-                    Box::from_raw(raw_ret as *mut Result<Vec<WasmValue>, RuntimeError>);
-                return *instance_data;
+        // Join every thread that was actually spawned before returning, even if one of
+        // them failed, so a later error never leaks an earlier job's thread.
+        let mut results = Vec::with_capacity(handles.len());
+        let mut join_err = None;
+        for handle in handles {
+            match handle {
+                Some(handle) => match handle.join() {
+                    Ok(value) => results.push(Some(value)),
+                    Err(err) => {
+                        join_err.get_or_insert(err);
+                        results.push(None);
+                    }
+                },
+                None => results.push(None),
             }
         }
+
+        if let Some(err) = spawn_err.or(join_err) {
+            return Err(err);
+        }
+
+        Ok(results.into_iter().map(|value| value.unwrap()).collect())
     }
 }
 
@@ -148,7 +488,14 @@ struct ClosureWrapper {
 // pthread 回調函數
 unsafe extern "C" fn raw_closurescaller(arg: *mut c_void) -> *mut c_void {
     let wrapper = unsafe { Box::from_raw(arg as *mut ClosureWrapper) };
-    let result: Box<dyn Any + Send> = (wrapper.closure)(); // 呼叫閉包
+    // Calling the closure here runs across the pthread trampoline's `extern "C"` boundary;
+    // a panic must not be allowed to unwind across it, so it's caught and boxed as a
+    // `PanicSentinel` instead, which `join_inflight_thread` turns back into a `RuntimeError`.
+    let result: Box<dyn Any + Send> =
+        match std::panic::catch_unwind(std::panic::AssertUnwindSafe(wrapper.closure)) {
+            Ok(result) => result, // 呼叫閉包
+            Err(payload) => Box::new(PanicSentinel(panic_message(&*payload))) as Box<dyn Any + Send>,
+        };
     Box::into_raw(result) as *mut c_void
 }
 
@@ -216,15 +563,47 @@ unsafe extern "C" fn raw_closurescaller(arg: *mut c_void) -> *mut c_void {
 /// In this example, the closure initializes a WAMR runtime, loads a module, creates an instance,
 /// and calls an exported function, all within a pthread context with a stack size of 4096 bytes.
 /// The result is returned as a `Vec<WasmValue>` wrapped in a `Result`.
-pub fn call_pthread<F, T>(stacksize: i32, f: F) -> Result<T, RuntimeError>
+///
+/// Internally this is a thin wrapper around [`spawn_pthread`] followed by
+/// [`PThreadHandle::join`]; use `spawn_pthread` directly to run the closure in the
+/// background and collect its result later.
+pub fn call_pthread<F, T>(config: impl Into<PThreadConfig>, f: F) -> Result<T, RuntimeError>
 where
     F: FnOnce() -> T + Send + 'static,
     T: Send + 'static,
 {
+    spawn_pthread(config, f)?.join()
+}
+
+/// Spawns a closure in a pthread context without blocking on its result.
+///
+/// This is the non-blocking counterpart to [`call_pthread`]: it creates the pthread and
+/// returns immediately with a [`PThreadHandle`], letting the caller fan out several WAMR
+/// calls before collecting any of their results. Handles track their own spawned thread,
+/// so they can be joined in any order.
+///
+/// # Parameters
+/// - `config`: The pthread's stack size, detach state, scheduling priority, and ESP32 core
+///   affinity, as a [`PThreadConfig`] (a bare `i32` stack size also works, as in
+///   `call_pthread`).
+/// - `f`: A closure that performs the desired operations and returns a value of type `T`.
+///
+/// # Errors
+/// Returns `Err(RuntimeError::ExecutionError)` if the pthread cannot be created (e.g., due
+/// to resource limitations) or the requested priority/core affinity cannot be applied.
+pub fn spawn_pthread<F, T>(
+    config: impl Into<PThreadConfig>,
+    f: F,
+) -> Result<PThreadHandle<T>, RuntimeError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let config = config.into();
+    apply_pthread_cfg(&config)?;
+
     let mut thread: u32 = 0;
-    let mut attr = esp_idf_svc::sys::pthread_attr_t::default();
-    attr.stacksize = 4096;
-    attr.detachstate = esp_idf_svc::sys::PTHREAD_CREATE_JOINABLE as i32;
+    let attr = build_pthread_attr(&config);
 
     let wrapped_f = move || {
         let result = f();
@@ -245,25 +624,309 @@ where
             message: format!("Failed to create thread: {}", res),
             exit_code: res.abs() as u32,
         }));
-    } else {
-        unsafe {
-            let mut raw_ret = std::ptr::null_mut();
-            let join_ret = esp_idf_svc::sys::pthread_join(thread as _, &mut raw_ret);
-            if join_ret != 0 {
-                return Err(RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
-                    message: format!("Failed to join thread: {}", join_ret),
-                    exit_code: join_ret.abs() as u32,
-                }));
+    }
+
+    Ok(PThreadHandle {
+        id: if config.detached {
+            next_detached_handle_id()
+        } else {
+            register_inflight_thread(thread)
+        },
+        detached: config.detached,
+        _marker: PhantomData,
+    })
+}
+
+/// One job in a [`call_pthread_parallel`] batch: a closure plus an optional ESP32 core id
+/// to pin its pthread to.
+pub struct PThreadJob<F> {
+    pub closure: F,
+    pub core_id: Option<i32>,
+}
+
+impl<F> From<F> for PThreadJob<F> {
+    /// Wraps a bare closure as a job with no core affinity.
+    fn from(closure: F) -> Self {
+        PThreadJob {
+            closure,
+            core_id: None,
+        }
+    }
+}
+
+impl<F> PThreadJob<F> {
+    /// Builds a job pinned to a specific ESP32 core.
+    pub fn pinned_to_core(closure: F, core_id: i32) -> Self {
+        PThreadJob {
+            closure,
+            core_id: Some(core_id),
+        }
+    }
+}
+
+/// Runs a batch of closures on their own pthreads concurrently, joins all of them, and
+/// returns their results in the same order the jobs were given.
+///
+/// This is the batch counterpart to [`call_pthread`]/[`spawn_pthread`]: each job gets its
+/// own pinned-or-unpinned pthread so independent WAMR calls can run on both ESP32 cores at
+/// once instead of being serialized. If any job's thread fails to create or join, the rest
+/// of the already-spawned threads are still joined (so none of them leak), and the first
+/// error encountered is returned.
+///
+/// # Parameters
+/// - `config`: The base config used for every job's pthread; a job's own `core_id`, if
+///   set, overrides this config's core affinity for just that job.
+/// - `jobs`: The closures to run, each optionally wrapped in a [`PThreadJob`] to pin it to
+///   an ESP32 core. Bare closures convert into unpinned jobs via `Into<PThreadJob<F>>`.
+pub fn call_pthread_parallel<F, T, J>(
+    config: impl Into<PThreadConfig>,
+    jobs: Vec<J>,
+) -> Result<Vec<T>, RuntimeError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+    J: Into<PThreadJob<F>>,
+{
+    let config = config.into();
+    let mut handles: Vec<Option<PThreadHandle<T>>> = Vec::with_capacity(jobs.len());
+    let mut spawn_err = None;
+
+    for job in jobs {
+        if spawn_err.is_some() {
+            handles.push(None);
+            continue;
+        }
+
+        let job = job.into();
+        let mut job_config = config;
+        if let Some(core_id) = job.core_id {
+            job_config = job_config.with_core_id(core_id);
+        }
+        match spawn_pthread(job_config, job.closure) {
+            Ok(handle) => handles.push(Some(handle)),
+            Err(err) => {
+                spawn_err = Some(err);
+                handles.push(None);
             }
+        }
+    }
+
+    // Join every thread that was actually spawned before returning, even if one of them
+    // failed, so a later error never leaks an earlier job's thread.
+    let mut results = Vec::with_capacity(handles.len());
+    let mut join_err = None;
+    for handle in handles {
+        match handle {
+            Some(handle) => match handle.join() {
+                Ok(value) => results.push(Some(value)),
+                Err(err) => {
+                    join_err.get_or_insert(err);
+                    results.push(None);
+                }
+            },
+            None => results.push(None),
+        }
+    }
+
+    if let Some(err) = spawn_err.or(join_err) {
+        return Err(err);
+    }
 
-            let boxed_result: Box<dyn Any + Send> = Box::from_raw(raw_ret as *mut _);
-            let result_boxed_t: Box<T> = boxed_result.downcast::<T>().map_err(|_| {
+    Ok(results.into_iter().map(|value| value.unwrap()).collect())
+}
+
+/// A job queued onto a [`PThreadReactor`]'s worker thread.
+enum ReactorJob {
+    /// Run a closure, sending its boxed result back on the paired reply channel.
+    Call(
+        Box<dyn FnOnce() -> Box<dyn Any + Send> + Send>,
+        std::sync::mpsc::Sender<Box<dyn Any + Send>>,
+    ),
+    /// Sentinel telling the worker loop to stop.
+    Shutdown,
+}
+
+/// A long-lived pthread that executes closures handed to it over a channel instead of
+/// spawning (and joining) a fresh pthread for every call.
+///
+/// Creating a pthread on every `call_pthread` is expensive on an ESP32 and re-enters the
+/// WAMR context each time. `PThreadReactor` spawns one pthread up front and keeps it
+/// parked reading jobs from an `mpsc` channel, so the cost of thread setup is paid once
+/// and every WAMR call after that runs on the same correctly-sized stack.
+///
+/// A typical use is to build the WAMR `Runtime`/`Instance` inside the first job sent to
+/// the reactor and keep them alive in state the later jobs' closures can reach (e.g. a
+/// `thread_local!` or a value moved into the first closure and re-captured by reference
+/// for subsequent ones via a shared cell).
+pub struct PThreadReactor {
+    sender: Option<std::sync::mpsc::Sender<ReactorJob>>,
+    handle: Option<PThreadHandle<()>>,
+}
+
+impl PThreadReactor {
+    /// Spawns the reactor's worker pthread and starts its job loop.
+    pub fn new(stacksize: i32) -> Result<Self, RuntimeError> {
+        let (sender, receiver) = std::sync::mpsc::channel::<ReactorJob>();
+
+        let worker = move || {
+            while let Ok(job) = receiver.recv() {
+                match job {
+                    ReactorJob::Call(f, reply) => {
+                        let _ = reply.send(f());
+                    }
+                    ReactorJob::Shutdown => break,
+                }
+            }
+        };
+
+        let handle = spawn_pthread(stacksize, worker)?;
+
+        Ok(PThreadReactor {
+            sender: Some(sender),
+            handle: Some(handle),
+        })
+    }
+
+    /// Enqueues `f` on the reactor's worker thread and blocks until it has run, returning
+    /// its result.
+    ///
+    /// # Errors
+    /// Returns `Err(RuntimeError::ExecutionError)` if the worker thread has already shut
+    /// down (e.g. it panicked) and can't accept or reply to the job.
+    pub fn call<F, T>(&self, f: F) -> Result<T, RuntimeError>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let wrapped = move || Box::new(f()) as Box<dyn Any + Send>;
+        let (reply_tx, reply_rx) = std::sync::mpsc::channel();
+
+        self.sender
+            .as_ref()
+            .expect("PThreadReactor sender missing")
+            .send(ReactorJob::Call(Box::new(wrapped), reply_tx))
+            .map_err(|_| {
                 RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
-                    message: "Type mismatch in thread result".to_string(),
+                    message: "PThreadReactor worker thread has already shut down".to_string(),
                     exit_code: 1,
                 })
             })?;
-            Ok(*result_boxed_t)
+
+        let raw_result = reply_rx.recv().map_err(|_| {
+            RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
+                message: "PThreadReactor worker thread did not reply".to_string(),
+                exit_code: 1,
+            })
+        })?;
+
+        raw_result.downcast::<T>().map(|boxed| *boxed).map_err(|_| {
+            RuntimeError::ExecutionError(wamr_rust_sdk::ExecError {
+                message: "Type mismatch in reactor result".to_string(),
+                exit_code: 1,
+            })
+        })
+    }
+}
+
+impl Drop for PThreadReactor {
+    /// Sends the shutdown sentinel and joins the worker thread so it never outlives the
+    /// reactor.
+    fn drop(&mut self) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(ReactorJob::Shutdown);
         }
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Wraps a raw pointer so it can be moved into a `Send` closure. Safe as long as the
+/// pointee is only ever accessed from one thread at a time, which is the case everywhere
+/// this is used in this crate.
+struct SendPtr(*mut c_void);
+unsafe impl Send for SendPtr {}
+
+/// The finalizer for a [`HostFnEnv`]'s boxed environment, reclaiming the `Box<E>` that
+/// [`register_host_fn_with_env`] allocated.
+extern "C" fn drop_boxed_env<E>(env: *mut c_void) {
+    unsafe {
+        drop(Box::from_raw(env as *mut E));
     }
 }
+
+/// Owns the environment registered alongside a host function by
+/// [`register_host_fn_with_env`], and reclaims it deterministically on `Drop`.
+///
+/// Mirrors Wasmtime's `wasmtime_func_new` env+finalizer contract: `env` is the opaque
+/// pointer the host function's implementation receives, and `finalizer` is the only thing
+/// responsible for dropping whatever it points to once the registration goes away.
+pub struct HostFnEnv {
+    env: *mut c_void,
+    finalizer: extern "C" fn(*mut c_void),
+}
+
+impl Drop for HostFnEnv {
+    fn drop(&mut self) {
+        (self.finalizer)(self.env);
+    }
+}
+
+/// Registers a host function together with a typed, per-instance environment, from inside
+/// a pthread context.
+///
+/// WAMR setup — registering host functions, building instances — must happen in a pthread
+/// context on platforms like the ESP32, which is this crate's whole reason to exist. But
+/// the plain `Runtime::builder().register_host_function(name, fn_ptr)` pattern used
+/// elsewhere in this crate has no way to carry state to the function it registers. This
+/// helper boxes `env`, runs `register` inside a spawned pthread with the raw environment
+/// pointer (so `register` can pass it through as the function's attachment), and returns a
+/// [`HostFnEnv`] whose `Drop` reclaims the box once the registration is torn down, rather
+/// than leaking it.
+///
+/// # Parameters
+/// - `config`: The pthread config used to run `register` in, as in [`call_pthread`].
+/// - `name`: The host function name being registered, forwarded to `register` for
+///   convenience.
+/// - `env`: The per-instance state to box and hand to `register` as a raw pointer.
+/// - `register`: Runs inside the pthread and performs the actual WAMR registration (e.g.
+///   via `Runtime::builder().register_host_function(..)`), receiving `name` and the boxed
+///   environment's raw pointer to pass through as attachment.
+///
+/// # Errors
+/// Returns `Err(RuntimeError::ExecutionError)` if the pthread that runs `register` cannot
+/// be created or joined, or if `register` panics.
+pub fn register_host_fn_with_env<E, F>(
+    config: impl Into<PThreadConfig>,
+    name: &'static str,
+    env: E,
+    register: F,
+) -> Result<HostFnEnv, RuntimeError>
+where
+    E: Send + 'static,
+    F: FnOnce(&'static str, *mut c_void) + Send + 'static,
+{
+    let raw_env = Box::into_raw(Box::new(env)) as *mut c_void;
+    // `*mut c_void` isn't `Send`, but the box it points to is only ever touched from one
+    // thread at a time (the pthread that runs `register`, then later whichever thread
+    // drops `HostFnEnv`), so it's safe to ferry the raw pointer across the spawn boundary.
+    let env_ptr = SendPtr(raw_env);
+
+    // Capturing `env_ptr` whole (rather than projecting straight to its `.0` field) is
+    // required here: Rust 2021's disjoint closure capture would otherwise capture the
+    // `*mut c_void` field directly, which isn't `Send`, defeating the `SendPtr` wrapper.
+    if let Err(err) = call_pthread(config, move || {
+        let env_ptr = env_ptr;
+        register(name, env_ptr.0)
+    }) {
+        // The pthread could not be created or joined, or `register` panicked in it; no
+        // `HostFnEnv` is returned to reclaim the box, so do it here instead of leaking it.
+        drop_boxed_env::<E>(raw_env);
+        return Err(err);
+    }
+
+    Ok(HostFnEnv {
+        env: raw_env,
+        finalizer: drop_boxed_env::<E>,
+    })
+}